@@ -1,4 +1,4 @@
-use crate::{DirtyChunks, EmptyChunks, ThreadLocalVoxelCache, Voxel, VoxelMap};
+use crate::{DirtyChunks, EmptyChunks, SignedDistance, ThreadLocalVoxelCache, Voxel, VoxelMap};
 
 use building_blocks::{prelude::*, search::OctreeDBVT, storage::octree::OctreeSet};
 
@@ -17,21 +17,41 @@ pub struct BVTPlugin<V> {
 impl<V> BVTPlugin<V>
 where
     V: Voxel,
-    for<'r> &'r V::TypeInfo: IsEmpty,
 {
     pub fn initialize(commands: &mut Commands) {
         commands.insert_resource(VoxelBVT::default());
     }
+}
 
+impl<V> BVTPlugin<V>
+where
+    V: Voxel,
+    for<'r> &'r V::TypeInfo: IsEmpty,
+{
+    /// Registers the default generator, which classifies occupancy from each voxel type's
+    /// `IsEmpty` info (blocky geometry).
     pub fn update_in_stage(stage: &mut SystemStage) {
         stage.add_system(octree_generator_system::<V>.system());
     }
 }
 
+impl<V> BVTPlugin<V>
+where
+    V: Voxel,
+    V: SignedDistance,
+{
+    /// Registers the SDF generator, which classifies a voxel as occupied when its signed distance
+    /// has reached the surface (`distance() <= 0.0`) instead of relying on `IsEmpty`. Use this for
+    /// voxel types that carry a signed-distance channel.
+    pub fn update_in_stage_sdf(stage: &mut SystemStage) {
+        stage.add_system(sdf_octree_generator_system::<V>.system());
+    }
+}
+
 /// An `OctreeDBVT` that maps chunk keys to chunk `OctreeSet`s.
 pub type VoxelBVT = OctreeDBVT<Point3i>;
 
-/// Generates new octrees for all edited chunks.
+/// Generates new octrees for all edited chunks, treating a voxel as solid via its `TypeInfo`.
 fn octree_generator_system<V>(
     pool: Res<ComputeTaskPool>,
     voxel_map: Res<VoxelMap<V>>,
@@ -43,9 +63,41 @@ fn octree_generator_system<V>(
     V: Voxel,
     for<'r> &'r V::TypeInfo: IsEmpty,
 {
-    let new_chunk_octrees =
-        generate_octree_for_each_chunk(&*dirty_chunks, &*voxel_map, &*local_caches, &*pool);
+    let new_chunk_octrees = generate_octree_for_each_chunk(&*dirty_chunks, &*pool, |map, chunk| {
+        let transform_chunk = TransformMap::new(&chunk.array, map.voxel_info_transform());
+        OctreeSet::from_array3(&transform_chunk, *chunk.array.extent())
+    }, &*voxel_map, &*local_caches);
+
+    insert_octrees(new_chunk_octrees, &mut voxel_bvt, &mut empty_chunks);
+}
 
+/// Generates new octrees for all edited chunks, treating a voxel as occupied when its signed
+/// distance has reached the surface.
+fn sdf_octree_generator_system<V>(
+    pool: Res<ComputeTaskPool>,
+    voxel_map: Res<VoxelMap<V>>,
+    local_caches: Res<ThreadLocalVoxelCache<V>>,
+    dirty_chunks: Res<DirtyChunks>,
+    mut voxel_bvt: ResMut<VoxelBVT>,
+    mut empty_chunks: ResMut<EmptyChunks>,
+) where
+    V: Voxel,
+    V: SignedDistance,
+{
+    let new_chunk_octrees = generate_octree_for_each_chunk(&*dirty_chunks, &*pool, |map, chunk| {
+        // The `IsEmpty` impl on the `Sd16` view reports empty exactly where `distance() > 0.0`.
+        let transform_chunk = TransformMap::new(&chunk.array, map.sdf_transform());
+        OctreeSet::from_array3(&transform_chunk, *chunk.array.extent())
+    }, &*voxel_map, &*local_caches);
+
+    insert_octrees(new_chunk_octrees, &mut voxel_bvt, &mut empty_chunks);
+}
+
+fn insert_octrees(
+    new_chunk_octrees: Vec<(Point3i, OctreeSet)>,
+    voxel_bvt: &mut VoxelBVT,
+    empty_chunks: &mut EmptyChunks,
+) {
     for (chunk_key, octree) in new_chunk_octrees.into_iter() {
         if octree.is_empty() {
             voxel_bvt.remove(&chunk_key);
@@ -58,26 +110,23 @@ fn octree_generator_system<V>(
 
 fn generate_octree_for_each_chunk<V>(
     dirty_chunks: &DirtyChunks,
+    pool: &TaskPool,
+    make_octree: impl Fn(&VoxelMap<V>, &Chunk3<V, ()>) -> OctreeSet + Send + Sync,
     map: &VoxelMap<V>,
     local_caches: &ThreadLocalVoxelCache<V>,
-    pool: &TaskPool,
 ) -> Vec<(Point3i, OctreeSet)>
 where
     V: Voxel,
-    for<'r> &'r V::TypeInfo: IsEmpty,
 {
+    let make_octree = &make_octree;
     pool.scope(|s| {
         for chunk_key in dirty_chunks.edited_chunk_keys.clone().into_iter() {
             s.spawn(async move {
                 let cache_tls = local_caches.get();
                 let reader = map.reader(&cache_tls);
                 let chunk = reader.get_chunk(chunk_key).unwrap();
-                let transform_chunk = TransformMap::new(&chunk.array, map.voxel_info_transform());
 
-                (
-                    chunk_key,
-                    OctreeSet::from_array3(&transform_chunk, *chunk.array.extent()),
-                )
+                (chunk_key, make_octree(map, chunk))
             })
         }
     })
@@ -0,0 +1,282 @@
+use crate::{DirtyChunks, ThreadLocalVoxelCache, Voxel, VoxelMap};
+
+use building_blocks::{
+    prelude::*,
+    storage::octree::{
+        clipmap::{clipmap_chunks_to_activate, ClipMapConfig3},
+        ChunkedOctreeSet,
+    },
+};
+
+use bevy::{
+    prelude::*,
+    tasks::{ComputeTaskPool, TaskPool},
+};
+
+/// Maintains a multi-resolution pyramid of downsampled chunks and emits `LodChange` events for the
+/// set of chunks that should be active at each level of detail, given a focal `Point3i`. Depends on
+/// the `MapIoPlugin`.
+///
+/// High detail is kept near the focus and coarser representations farther out. Downstream meshing or
+/// BVT systems listen for `LodChange` events to swap chunk representations.
+#[derive(Default)]
+pub struct LodPlugin<V> {
+    marker: std::marker::PhantomData<V>,
+}
+
+impl<V> LodPlugin<V>
+where
+    V: Voxel,
+    V: MeanDownsampleable + PointDownsampleable,
+{
+    pub fn initialize(commands: &mut Commands, chunk_shape: Point3i, num_lods: u8, clip_radius: i32) {
+        commands.insert_resource(VoxelLodMap::<V>::new(chunk_shape, num_lods, clip_radius));
+        commands.insert_resource(LodFocus::default());
+        commands.insert_resource(Events::<LodChange>::default());
+    }
+
+    pub fn update_in_stage(stage: &mut SystemStage) {
+        stage.add_system(lod_generator_system::<V>.system());
+    }
+}
+
+/// A level-of-detail chunk key: a LOD-0 `Point3i` chunk key together with its `lod`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LodChunkKey {
+    pub chunk_key: Point3i,
+    pub lod: u8,
+}
+
+/// Emitted when a chunk enters or exits the active set at some LOD. Downstream systems swap the
+/// chunk's rendered/collided representation in response.
+#[derive(Clone, Copy, Debug)]
+pub enum LodChange {
+    /// The chunk became active at this LOD and should be realized.
+    Enter(LodChunkKey),
+    /// The chunk is no longer active at this LOD and should be torn down.
+    Exit(LodChunkKey),
+}
+
+/// A pyramid of downsampled chunk arrays plus an index of which LOD-0 chunks exist.
+///
+/// Level 0 aliases the `VoxelMap`'s chunks; each coarser level stores the averaged/majority
+/// downsample of the finer level, written into the corresponding parent chunk array.
+pub struct VoxelLodMap<V>
+where
+    V: Voxel,
+{
+    /// Downsampled chunks for LODs `1..num_lods`. LOD 0 lives in the `VoxelMap`.
+    pub lods: Vec<ChunkHashMap3<V>>,
+    /// Tracks which LOD-0 chunks are resident, so the clipmap query knows what can be activated.
+    pub index: OctreeChunkIndex,
+    clip_config: ClipMapConfig3,
+    active: SmallKeyHashSet<LodChunkKey>,
+}
+
+/// A `ChunkedOctreeSet` over LOD-0 chunk keys, used to drive the clipmap query.
+pub type OctreeChunkIndex = ChunkedOctreeSet;
+
+impl<V> VoxelLodMap<V>
+where
+    V: Voxel,
+{
+    fn new(chunk_shape: Point3i, num_lods: u8, clip_radius: i32) -> Self {
+        let lods = (1..num_lods)
+            .map(|_| crate::empty_chunk_hash_map(chunk_shape))
+            .collect();
+
+        Self {
+            lods,
+            index: ChunkedOctreeSet::new_empty(chunk_shape),
+            clip_config: ClipMapConfig3::new(num_lods, clip_radius, chunk_shape),
+            active: SmallKeyHashSet::new(),
+        }
+    }
+
+    fn num_lods(&self) -> u8 {
+        self.clip_config.num_lods()
+    }
+}
+
+/// Voxels whose type-index channel can be collapsed over a 2×2×2 block by picking the majority
+/// representative. Averaging type indices is meaningless, so we vote instead.
+pub trait PointDownsampleable: Copy {
+    fn majority(block: &[Self; 8]) -> Self;
+}
+
+/// Voxels whose SDF channel can be averaged over a 2×2×2 block. The type index is chosen separately
+/// by `majority`, so this only overlays the mean distance onto an already-chosen representative.
+pub trait MeanDownsampleable: PointDownsampleable {
+    fn with_mean_distance(self, block: &[Self; 8]) -> Self;
+}
+
+/// Regenerates the LOD pyramid for any dirty chunks and activates/deactivates chunks around the
+/// focal point, emitting `LodChange` events.
+fn lod_generator_system<V>(
+    pool: Res<ComputeTaskPool>,
+    voxel_map: Res<VoxelMap<V>>,
+    local_caches: Res<ThreadLocalVoxelCache<V>>,
+    dirty_chunks: Res<DirtyChunks>,
+    focus: Res<LodFocus>,
+    mut lod_map: ResMut<VoxelLodMap<V>>,
+    mut lod_changes: ResMut<Events<LodChange>>,
+) where
+    V: Voxel,
+    V: MeanDownsampleable + PointDownsampleable,
+{
+    // Pull any freshly edited LOD-0 chunks into the index and regenerate their ancestors.
+    for &chunk_key in dirty_chunks.edited_chunk_keys.iter() {
+        lod_map.index.insert_chunk(chunk_key);
+    }
+    regenerate_ancestors(&*dirty_chunks, &*voxel_map, &*local_caches, &*pool, &mut *lod_map);
+
+    // Ask the clipmap which chunks should be active at each LOD this frame.
+    let mut should_be_active = SmallKeyHashSet::new();
+    clipmap_chunks_to_activate(
+        &lod_map.clip_config,
+        &lod_map.index,
+        focus.center,
+        |chunk_key, lod| {
+            should_be_active.insert(LodChunkKey { chunk_key, lod });
+        },
+    );
+
+    for &key in should_be_active.iter() {
+        if lod_map.active.insert(key) {
+            lod_changes.send(LodChange::Enter(key));
+        }
+    }
+    lod_map.active.retain(|&key| {
+        let keep = should_be_active.contains(&key);
+        if !keep {
+            lod_changes.send(LodChange::Exit(key));
+        }
+        keep
+    });
+}
+
+/// Re-downsamples every LOD-0 chunk marked dirty, walking up the pyramid so that each coarser
+/// ancestor reflects the new fine data. The LOD 0 -> LOD 1 averaging runs per chunk on the
+/// `ComputeTaskPool`; the coarser levels are propagated on the calling thread.
+fn regenerate_ancestors<V>(
+    dirty_chunks: &DirtyChunks,
+    map: &VoxelMap<V>,
+    local_caches: &ThreadLocalVoxelCache<V>,
+    pool: &TaskPool,
+    lod_map: &mut VoxelLodMap<V>,
+) where
+    V: Voxel,
+    V: MeanDownsampleable + PointDownsampleable,
+{
+    let num_lods = lod_map.num_lods();
+    if num_lods < 2 {
+        return;
+    }
+
+    let chunk_shape = map.voxels.indexer.chunk_shape();
+    let half_shape = chunk_shape >> 1;
+
+    // Downsample LOD 0 -> LOD 1 for each dirty chunk in parallel. Each child collapses to a
+    // half-shape array positioned at the octant of its parent that it occupies.
+    let downsampled = pool.scope(|s| {
+        for chunk_key in dirty_chunks.edited_chunk_keys.clone().into_iter() {
+            s.spawn(async move {
+                let cache_tls = local_caches.get();
+                let reader = map.reader(&cache_tls);
+                let chunk = reader.get_chunk(chunk_key).unwrap();
+                let (parent_key, octant_min) = parent_and_octant(chunk_key, chunk_shape, half_shape);
+                (parent_key, downsample_into(&chunk.array, octant_min, half_shape))
+            })
+        }
+    });
+
+    // Write each downsampled octant into its parent without clobbering the other seven.
+    let mut dirty_parents = SmallKeyHashSet::new();
+    for (parent_key, octant) in downsampled.into_iter() {
+        write_octant(&mut lod_map.lods[0], parent_key, &octant);
+        dirty_parents.insert(parent_key);
+    }
+
+    // Propagate up through the coarser levels, one octant per child.
+    for lod in 1..(num_lods - 1) as usize {
+        let mut next_dirty = SmallKeyHashSet::new();
+        for child_key in dirty_parents.iter().copied() {
+            // Clone out of the finer level so we can borrow the coarser level mutably.
+            let child_array = match lod_map.lods[lod - 1].get_chunk(child_key) {
+                Some(chunk) => chunk.array.clone(),
+                None => continue,
+            };
+            let (parent_key, octant_min) = parent_and_octant(child_key, chunk_shape, half_shape);
+            let octant = downsample_into(&child_array, octant_min, half_shape);
+            write_octant(&mut lod_map.lods[lod], parent_key, &octant);
+            next_dirty.insert(parent_key);
+        }
+        dirty_parents = next_dirty;
+    }
+}
+
+/// Returns the parent chunk key of `child_key` and the world-space minimum of the octant within that
+/// parent that `child_key` downsamples into. Eight children share one parent, one per octant.
+fn parent_and_octant(child_key: Point3i, chunk_shape: Point3i, half_shape: Point3i) -> (Point3i, Point3i) {
+    let coord = PointN([
+        child_key.x().div_euclid(chunk_shape.x()),
+        child_key.y().div_euclid(chunk_shape.y()),
+        child_key.z().div_euclid(chunk_shape.z()),
+    ]);
+    let parent_key = PointN([
+        (coord.x() >> 1) * chunk_shape.x(),
+        (coord.y() >> 1) * chunk_shape.y(),
+        (coord.z() >> 1) * chunk_shape.z(),
+    ]);
+    let octant_min = parent_key
+        + PointN([
+            coord.x().rem_euclid(2) * half_shape.x(),
+            coord.y().rem_euclid(2) * half_shape.y(),
+            coord.z().rem_euclid(2) * half_shape.z(),
+        ]);
+
+    (parent_key, octant_min)
+}
+
+/// Copies a downsampled octant array into its parent chunk, creating the parent (filled with the
+/// ambient value) on first touch so the other octants are preserved across frames.
+fn write_octant<V>(lod: &mut ChunkHashMap3<V>, parent_key: Point3i, octant: &Array3<V>)
+where
+    V: Voxel,
+{
+    let parent = lod.get_mut_chunk_or_insert_ambient(parent_key);
+    copy_extent(octant.extent(), octant, &mut parent.array);
+}
+
+/// Averages each 2×2×2 block of `src` into one voxel of a half-resolution array placed at
+/// `octant_min` — the world-space minimum of the octant this array occupies in its parent chunk.
+/// SDF channels use the mean, type indices use the majority representative.
+fn downsample_into<V>(src: &Array3<V>, octant_min: Point3i, half_shape: Point3i) -> Array3<V>
+where
+    V: Voxel,
+    V: MeanDownsampleable + PointDownsampleable,
+{
+    let dst_extent = Extent3i::from_min_and_shape(octant_min, half_shape);
+    let mut dst = crate::default_array::<V>(dst_extent);
+
+    dst.for_each_mut(&dst_extent, |p: Point3i, v| {
+        let base = src.extent().minimum + ((p - octant_min) << 1);
+        let mut block = [V::default(); 8];
+        for (i, offset) in Point3i::corner_offsets().iter().enumerate() {
+            block[i] = src.get(base + *offset);
+        }
+        // Type index by majority vote, SDF by mean: route each channel through its own downsampler.
+        *v = V::majority(&block).with_mean_distance(&block);
+    });
+
+    dst
+}
+
+/// The focal point (e.g. the camera) that the clipmap keeps high detail around.
+#[derive(Clone, Copy, Default)]
+pub struct LodFocus {
+    pub center: Point3i,
+}
+
+// Re-exported alongside the LOD types for downstream users; nothing in this module uses it.
+pub use building_blocks::storage::octree::clipmap::LodChunkUpdate3 as LodChunkUpdate;
@@ -1,6 +1,6 @@
-use crate::{ThreadLocalResourceHandle, Voxel};
+use crate::{ChunkDedupCache, DedupStats, Sd16, SignedDistance, ThreadLocalResourceHandle, Voxel};
 
-use building_blocks::prelude::*;
+use building_blocks::{prelude::*, storage::FastLz4};
 
 /// The global source of truth for voxels in the current map.
 ///
@@ -43,14 +43,22 @@ use building_blocks::prelude::*;
 ///             MyVoxelTypeInfo { is_empty: false },
 ///         ],
 ///     },
+///     dedup: Default::default(),
 /// };
 /// ```
+/// The compressed form of a chunk as stored by the `CompressibleChunkMap3`.
+pub type CompressedChunk<V> = Compressed<FastLz4, Array3<V>>;
+
 pub struct VoxelMap<V>
 where
     V: Voxel,
 {
     pub voxels: CompressibleChunkMap3<V>,
     pub palette: VoxelPalette<V::TypeInfo>,
+    /// Interns identical compressed chunks so repetitive regions share one buffer. Owns the
+    /// compressed tier for deduplicated keys. Only populated when `ChunkCacheConfig.enable_dedup`
+    /// is set.
+    pub dedup: ChunkDedupCache<CompressedChunk<V>>,
 }
 
 impl<V> VoxelMap<V>
@@ -64,6 +72,24 @@ where
         move |v: V| self.palette.get_voxel_type_info(v)
     }
 
+    /// Returns a closure that quantizes each voxel's signed distance into an `Sd16`. This is the
+    /// companion to `voxel_info_transform` for SDF voxels: wrap it in a `TransformMap` to read the
+    /// map as an `Sd16` field suitable for feeding a surface-nets or dual-contouring mesher.
+    #[inline]
+    pub fn sdf_transform(&self) -> impl Fn(V) -> Sd16
+    where
+        V: SignedDistance,
+    {
+        |v: V| Sd16::from(v.distance())
+    }
+
+    /// Returns the unique-vs-total compressed chunk counts from the dedup layer, for measuring
+    /// memory savings on repetitive worlds. The totals are zero unless `enable_dedup` is set.
+    #[inline]
+    pub fn dedup_stats(&self) -> DedupStats {
+        self.dedup.stats()
+    }
+
     pub fn reader<'a>(
         &'a self,
         cache: &'a ThreadLocalResourceHandle<LocalChunkCache3<V>>,
@@ -1,10 +1,20 @@
 mod chunk_cache_flusher;
 mod chunk_compressor;
+mod chunk_dedup;
 mod edit_buffer;
+#[cfg(feature = "encryption")]
+mod encryption;
 mod editor;
+mod persistence;
 mod plugin;
+mod preload;
 
 pub use chunk_compressor::ChunkCacheConfig;
+pub use chunk_dedup::{ChunkDedupCache, DedupStats, Fingerprint};
+#[cfg(feature = "encryption")]
+pub use encryption::EncryptionConfig;
+pub use persistence::{ChunkDb, PersistenceConfig, PersistenceError, PersistencePlugin};
+pub use preload::PreloadHandle;
 pub use edit_buffer::{double_buffering_system, DirtyChunks, EditBuffer};
 pub use editor::VoxelEditor;
 pub use plugin::MapIoPlugin;
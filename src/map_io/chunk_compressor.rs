@@ -1,4 +1,4 @@
-use crate::{Voxel, VoxelMap};
+use crate::{Fingerprint, Voxel, VoxelMap};
 
 use bevy::{prelude::*, tasks::ComputeTaskPool};
 use building_blocks::storage::{Compressible, FastLz4};
@@ -8,6 +8,10 @@ pub struct ChunkCacheConfig {
     // These constants should be correlated with the size of a chunk.
     pub max_cached_chunks: usize,
     pub max_chunks_compressed_per_frame_per_thread: usize,
+    /// When enabled, identical compressed chunks are interned so they share a single buffer. This
+    /// saves memory on worlds with large repetitive regions (air, solid rock) at the cost of a
+    /// fingerprint hash per eviction.
+    pub enable_dedup: bool,
 }
 
 impl Default for ChunkCacheConfig {
@@ -18,12 +22,18 @@ impl Default for ChunkCacheConfig {
             // Avoid high latency from compressing too many chunks in one frame. 8192-byte chunk
             // compression latency is around 0.01 ms.
             max_chunks_compressed_per_frame_per_thread: 50,
+            enable_dedup: false,
         }
     }
 }
 
 /// A system that evicts and compresses the least recently used voxel chunks when the cache gets too
 /// big.
+///
+/// When the `encryption` feature is enabled, the cipher boundary sits just after compression, where
+/// chunks leave memory as bytes: the `PersistencePlugin`'s disk backend encrypts each compressed
+/// chunk on its way out and decrypts it lazily on fault-in, so the in-memory compressed pool pays no
+/// cost.
 pub fn chunk_compressor_system<V>(
     cache_config: Res<ChunkCacheConfig>,
     pool: Res<ComputeTaskPool>,
@@ -57,9 +67,17 @@ pub fn chunk_compressor_system<V>(
     });
 
     for (key, compressed_chunk) in compressed_chunks.into_iter() {
-        voxel_map
-            .voxels
-            .chunks
-            .insert_compressed(key, compressed_chunk);
+        if cache_config.enable_dedup {
+            // Intern by content: identical chunks share one Arc-backed buffer and the storage keeps
+            // only the fingerprint, so duplicates free memory. The dedup cache owns the compressed
+            // tier for these keys.
+            let fingerprint = Fingerprint::of(&compressed_chunk.compressed_bytes);
+            voxel_map.dedup.insert(key, fingerprint, compressed_chunk);
+        } else {
+            voxel_map
+                .voxels
+                .chunks
+                .insert_compressed(key, compressed_chunk);
+        }
     }
 }
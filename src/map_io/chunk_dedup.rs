@@ -0,0 +1,199 @@
+use building_blocks::core::Point3i;
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// A 128-bit content fingerprint of a chunk's compressed bytes. Two chunks that compress to the same
+/// bytes share a fingerprint, and therefore a single buffer.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Fingerprint(pub u128);
+
+impl Fingerprint {
+    /// Computes a 128-bit fingerprint of `bytes` by hashing with two differently-seeded hashers and
+    /// concatenating the results.
+    pub fn of(bytes: &[u8]) -> Self {
+        let lo = seeded_hash(bytes, 0);
+        let hi = seeded_hash(bytes, 0x9E37_79B9_7F4A_7C15);
+        Fingerprint(((hi as u128) << 64) | lo as u128)
+    }
+}
+
+fn seeded_hash(bytes: &[u8], seed: u64) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    seed.hash(&mut hasher);
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+struct Interned<C> {
+    chunk: Arc<C>,
+    refcount: usize,
+}
+
+/// Interns compressed chunks by content so that identical chunks (large volumes of air or solid
+/// rock) share a single buffer.
+///
+/// When dedup is enabled, this cache *owns* the compressed tier: each resident key stores only a
+/// `Fingerprint`, and each distinct fingerprint owns one `Arc`-backed buffer shared by every key
+/// with that content. Interning an already-seen chunk drops the incoming buffer and bumps a
+/// refcount, so N identical chunks cost one buffer plus N small map entries. Buffers are freed when
+/// their last key is removed.
+pub struct ChunkDedupCache<C> {
+    interned: HashMap<Fingerprint, Interned<C>>,
+    keys: HashMap<Point3i, Fingerprint>,
+}
+
+impl<C> Default for ChunkDedupCache<C> {
+    fn default() -> Self {
+        Self {
+            interned: HashMap::new(),
+            keys: HashMap::new(),
+        }
+    }
+}
+
+/// Unique-versus-total compressed chunk counts, for measuring dedup savings on repetitive worlds.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DedupStats {
+    /// The number of distinct buffers actually held in memory (one per content fingerprint).
+    pub unique_chunks: usize,
+    /// The number of resident keys, counting shared buffers once per key.
+    pub total_chunks: usize,
+}
+
+impl<C> ChunkDedupCache<C> {
+    /// Interns the compressed chunk for `key`, returning the shared handle to the canonical buffer.
+    ///
+    /// If a chunk with the same `fingerprint` is already interned, `chunk` is dropped and the
+    /// existing buffer is shared; otherwise `chunk` becomes the canonical buffer. The single `Arc`
+    /// is the only copy of the bytes, so duplicates free memory rather than consume it.
+    pub fn insert(&mut self, key: Point3i, fingerprint: Fingerprint, chunk: C) -> Arc<C> {
+        if let Some(old) = self.keys.insert(key, fingerprint) {
+            if old == fingerprint {
+                return self.interned[&fingerprint].chunk.clone();
+            }
+            self.release(old);
+        }
+
+        let entry = self.interned.entry(fingerprint).or_insert_with(|| Interned {
+            chunk: Arc::new(chunk),
+            refcount: 0,
+        });
+        entry.refcount += 1;
+
+        entry.chunk.clone()
+    }
+
+    /// Returns the shared compressed buffer for `key`, if it is interned.
+    pub fn get(&self, key: Point3i) -> Option<Arc<C>> {
+        self.keys
+            .get(&key)
+            .and_then(|fingerprint| self.interned.get(fingerprint))
+            .map(|entry| entry.chunk.clone())
+    }
+
+    /// Drops the reference held by `key` — called both when a chunk is removed via `EmptyChunks`
+    /// and when `VoxelMap::get_chunk` moves it back into the compressed pool — freeing the shared
+    /// buffer once no key references it. Keeping this in lockstep with the compressed tier is what
+    /// keeps `dedup_stats` from drifting.
+    pub fn remove(&mut self, key: Point3i) {
+        if let Some(fingerprint) = self.keys.remove(&key) {
+            self.release(fingerprint);
+        }
+    }
+
+    fn release(&mut self, fingerprint: Fingerprint) {
+        if let Some(entry) = self.interned.get_mut(&fingerprint) {
+            entry.refcount -= 1;
+            if entry.refcount == 0 {
+                self.interned.remove(&fingerprint);
+            }
+        }
+    }
+
+    /// Returns the unique-vs-total chunk counts.
+    pub fn stats(&self) -> DedupStats {
+        DedupStats {
+            unique_chunks: self.interned.len(),
+            total_chunks: self.keys.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use building_blocks::core::PointN;
+
+    fn fp(bytes: &[u8]) -> Fingerprint {
+        Fingerprint::of(bytes)
+    }
+
+    #[test]
+    fn identical_bytes_share_a_fingerprint() {
+        assert_eq!(fp(&[1, 2, 3, 4]), fp(&[1, 2, 3, 4]));
+        assert_ne!(fp(&[1, 2, 3, 4]), fp(&[1, 2, 3, 5]));
+    }
+
+    #[test]
+    fn duplicates_share_one_buffer() {
+        let mut cache: ChunkDedupCache<Vec<u8>> = ChunkDedupCache::default();
+        let air = vec![0u8; 16];
+        let a = cache.insert(PointN([0, 0, 0]), fp(&air), air.clone());
+        let b = cache.insert(PointN([1, 0, 0]), fp(&air), air.clone());
+        let c = cache.insert(PointN([0, 1, 0]), fp(&air), air);
+
+        // All three keys point at the exact same allocation.
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(Arc::ptr_eq(&b, &c));
+        assert_eq!(
+            cache.stats(),
+            DedupStats {
+                unique_chunks: 1,
+                total_chunks: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn re_interning_changed_content_does_not_drift_totals() {
+        let mut cache: ChunkDedupCache<Vec<u8>> = ChunkDedupCache::default();
+        let key = PointN([0, 0, 0]);
+        for b in 0..10u8 {
+            let bytes = vec![b];
+            cache.insert(key, fp(&bytes), bytes);
+        }
+
+        assert_eq!(
+            cache.stats(),
+            DedupStats {
+                unique_chunks: 1,
+                total_chunks: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn removal_frees_the_buffer_at_zero_refs() {
+        let mut cache: ChunkDedupCache<Vec<u8>> = ChunkDedupCache::default();
+        let rock = vec![7u8; 8];
+        cache.insert(PointN([0, 0, 0]), fp(&rock), rock.clone());
+        cache.insert(PointN([1, 0, 0]), fp(&rock), rock);
+
+        cache.remove(PointN([0, 0, 0]));
+        assert_eq!(cache.stats().unique_chunks, 1);
+        assert!(cache.get(PointN([1, 0, 0])).is_some());
+
+        cache.remove(PointN([1, 0, 0]));
+        assert_eq!(
+            cache.stats(),
+            DedupStats {
+                unique_chunks: 0,
+                total_chunks: 0,
+            }
+        );
+        assert!(cache.get(PointN([0, 0, 0])).is_none());
+    }
+}
@@ -28,5 +28,6 @@ pub fn empty_chunk_remover_system<V>(
 {
     for chunk_key in empty_chunks.chunks_to_remove.drain(..) {
         voxel_map.voxels.storage_mut().remove(chunk_key);
+        voxel_map.dedup.remove(chunk_key);
     }
 }
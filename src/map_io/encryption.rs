@@ -0,0 +1,120 @@
+use chacha20poly1305::{
+    aead::{Aead, NewAead},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand::RngCore;
+
+/// The key used to encrypt evicted/persisted chunks. Supply this once as a resource; keep the
+/// default in-memory path unencrypted by leaving the `encryption` feature off.
+///
+/// # Where the cipher runs
+///
+/// Encryption is applied only at the disk boundary in the `PersistencePlugin` (on page-out, with
+/// decryption on fault-in), not inside `chunk_compressor_system`: the in-memory compressed pool is
+/// never decompressed through our code, so encrypting it would break lazy decompression. The cipher
+/// therefore sits exactly where compressed bytes leave memory.
+#[derive(Clone)]
+pub struct EncryptionConfig {
+    /// A 256-bit ChaCha20-Poly1305 key.
+    pub key: [u8; 32],
+}
+
+/// The reasons decryption of an on-disk chunk can fail: a blob too short to contain a nonce, or a
+/// failed authentication tag (corruption or the wrong key).
+#[derive(Clone, Copy, Debug)]
+pub enum DecryptError {
+    TooShort,
+    Authentication,
+}
+
+impl std::fmt::Display for DecryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DecryptError::TooShort => write!(f, "ciphertext too short to contain a nonce"),
+            DecryptError::Authentication => {
+                write!(f, "authentication failed (wrong key or corrupt chunk)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecryptError {}
+
+/// The size of the random nonce prepended to each ciphertext.
+const NONCE_LEN: usize = 12;
+
+/// Encrypts `plaintext` with ChaCha20-Poly1305, returning the fresh random nonce followed by the
+/// authenticated ciphertext. This sits just after LZ4 compression, so only already-compressed bytes
+/// are encrypted.
+pub fn encrypt(config: &EncryptionConfig, plaintext: &[u8]) -> Vec<u8> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&config.key));
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .expect("ChaCha20-Poly1305 encryption failed");
+
+    let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+
+    out
+}
+
+/// Reverses `encrypt`, splitting off the prepended nonce and verifying the authentication tag. This
+/// runs lazily on fault-in, before decompression. A truncated, corrupt, or wrong-key blob yields a
+/// `DecryptError` rather than panicking, so the error can be surfaced up the fault-in path.
+pub fn decrypt(config: &EncryptionConfig, bytes: &[u8]) -> Result<Vec<u8>, DecryptError> {
+    if bytes.len() < NONCE_LEN {
+        return Err(DecryptError::TooShort);
+    }
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&config.key));
+
+    let (nonce_bytes, ciphertext) = bytes.split_at(NONCE_LEN);
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| DecryptError::Authentication)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> EncryptionConfig {
+        EncryptionConfig { key: [42u8; 32] }
+    }
+
+    #[test]
+    fn encrypt_then_decrypt_round_trips() {
+        let config = test_config();
+        let plaintext = b"compressed chunk bytes";
+        let ciphertext = encrypt(&config, plaintext);
+        // The nonce is prepended, so the output is longer than the nonce alone.
+        assert!(ciphertext.len() > NONCE_LEN);
+        assert_eq!(decrypt(&config, &ciphertext).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn wrong_key_fails_authentication() {
+        let ciphertext = encrypt(&test_config(), b"secret terrain");
+        let other = EncryptionConfig { key: [7u8; 32] };
+        assert!(matches!(
+            decrypt(&other, &ciphertext),
+            Err(DecryptError::Authentication)
+        ));
+    }
+
+    #[test]
+    fn short_blob_is_rejected_not_panicked() {
+        assert!(matches!(
+            decrypt(&test_config(), &[0, 1, 2]),
+            Err(DecryptError::TooShort)
+        ));
+    }
+}
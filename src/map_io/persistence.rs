@@ -0,0 +1,313 @@
+use crate::{CompressedChunk, Voxel, VoxelMap};
+
+use bevy::{prelude::*, tasks::ComputeTaskPool};
+use building_blocks::{core::Point3i, storage::FastLz4};
+
+use std::path::PathBuf;
+
+/// Pages the coldest compressed chunks out to an on-disk block store once the in-memory compressed
+/// pool exceeds a byte budget, and faults them back in on demand. Depends on the `MapIoPlugin`, and
+/// should run after the `chunk_compressor_system` so it sees freshly compressed chunks.
+///
+/// This lets the crate stream worlds far larger than RAM: hot chunks stay in the compressed cache,
+/// while cold ones live on disk keyed by their `Point3i`.
+#[derive(Default)]
+pub struct PersistencePlugin<V> {
+    marker: std::marker::PhantomData<V>,
+}
+
+impl<V> PersistencePlugin<V>
+where
+    V: Voxel,
+{
+    pub fn initialize(commands: &mut Commands, config: PersistenceConfig) -> sled::Result<()> {
+        let db = ChunkDb::open(&config.db_path)?;
+        commands.insert_resource(db);
+        commands.insert_resource(config);
+
+        Ok(())
+    }
+
+    pub fn update_in_stage(stage: &mut SystemStage) {
+        // Paging is the only periodic system: it evicts the coldest chunks to disk when the pool
+        // grows past budget. Fault-in is *lazy* — it happens when a missing chunk is requested
+        // through `VoxelMap::get_chunk` (not through `reader`), so there's no fault-in/paging
+        // ordering to enforce here.
+        stage.add_system(chunk_paging_system::<V>.system());
+    }
+}
+
+#[derive(Clone)]
+pub struct PersistenceConfig {
+    /// Where the on-disk block store lives.
+    pub db_path: PathBuf,
+    /// Once the in-memory compressed pool exceeds this many bytes, the coldest chunks are paged out.
+    pub max_compressed_bytes: usize,
+    /// The number of compressed chunks to keep resident after paging out.
+    pub target_cached_chunks: usize,
+}
+
+impl Default for PersistenceConfig {
+    fn default() -> Self {
+        Self {
+            db_path: PathBuf::from("voxel_chunks.db"),
+            // A little under a gigabyte of compressed chunks before we start paging to disk.
+            max_compressed_bytes: 1 << 30,
+            target_cached_chunks: 50000,
+        }
+    }
+}
+
+/// The reasons a fault-in can fail: a backing-store error, or (with the `encryption` feature) a
+/// failure to decrypt the on-disk chunk.
+#[derive(Debug)]
+pub enum PersistenceError {
+    Db(sled::Error),
+    #[cfg(feature = "encryption")]
+    Decrypt(super::encryption::DecryptError),
+}
+
+impl std::fmt::Display for PersistenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PersistenceError::Db(e) => write!(f, "{}", e),
+            #[cfg(feature = "encryption")]
+            PersistenceError::Decrypt(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {}
+
+impl From<sled::Error> for PersistenceError {
+    fn from(e: sled::Error) -> Self {
+        PersistenceError::Db(e)
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl From<super::encryption::DecryptError> for PersistenceError {
+    fn from(e: super::encryption::DecryptError) -> Self {
+        PersistenceError::Decrypt(e)
+    }
+}
+
+/// A persistent key-value store mapping chunk `Point3i` keys to their compressed bytes.
+///
+/// Keys are encoded big-endian so that the backing B-tree keeps them in `Point3i` order, which makes
+/// the block-mapping index compact and lookups `O(log n)`.
+pub struct ChunkDb {
+    tree: sled::Db,
+}
+
+impl ChunkDb {
+    pub fn open(path: &PathBuf) -> sled::Result<Self> {
+        Ok(Self {
+            tree: sled::open(path)?,
+        })
+    }
+
+    /// Serializes the compressed bytes for `key` to disk.
+    pub fn save(&self, key: Point3i, bytes: &[u8]) -> sled::Result<()> {
+        self.tree.insert(encode_key(key), bytes)?;
+
+        Ok(())
+    }
+
+    /// Reads the compressed bytes for `key` back from disk, if present.
+    pub fn load(&self, key: Point3i) -> sled::Result<Option<Vec<u8>>> {
+        Ok(self.tree.get(encode_key(key))?.map(|ivec| ivec.to_vec()))
+    }
+
+    /// Flushes all pending writes to disk for a clean shutdown.
+    pub fn flush(&self) -> sled::Result<()> {
+        self.tree.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Encodes a chunk key as a 12-byte big-endian key so the store stays ordered by `Point3i`.
+fn encode_key(key: Point3i) -> [u8; 12] {
+    let mut bytes = [0; 12];
+    bytes[0..4].copy_from_slice(&key.x().to_be_bytes());
+    bytes[4..8].copy_from_slice(&key.y().to_be_bytes());
+    bytes[8..12].copy_from_slice(&key.z().to_be_bytes());
+
+    bytes
+}
+
+/// The total size, in bytes, of every compressed chunk currently held in the in-memory pool.
+fn compressed_pool_bytes<V>(voxel_map: &VoxelMap<V>) -> usize
+where
+    V: Voxel,
+{
+    voxel_map
+        .voxels
+        .chunks
+        .compressed_chunks()
+        .map(|compressed| compressed.compressed_bytes.len())
+        .sum()
+}
+
+/// Pages the coldest compressed chunks out to the `ChunkDb` when the in-memory pool grows past the
+/// configured byte budget. Serialization runs on the `ComputeTaskPool`.
+fn chunk_paging_system<V>(
+    config: Res<PersistenceConfig>,
+    pool: Res<ComputeTaskPool>,
+    db: Res<ChunkDb>,
+    #[cfg(feature = "encryption")] encryption: Res<crate::EncryptionConfig>,
+    mut voxel_map: ResMut<VoxelMap<V>>,
+) where
+    V: Voxel,
+{
+    let mut used = compressed_pool_bytes(&*voxel_map);
+    if used <= config.max_compressed_bytes {
+        return;
+    }
+
+    // Page out the coldest compressed chunks until the pool is back under the byte budget, keeping
+    // at least `target_cached_chunks` resident so hot chunks don't thrash to disk.
+    let mut to_page = Vec::new();
+    while used > config.max_compressed_bytes
+        && voxel_map.voxels.chunks.len_compressed() > config.target_cached_chunks
+    {
+        match voxel_map.voxels.chunks.remove_lru_compressed() {
+            Some((key, compressed)) => {
+                used -= compressed.compressed_bytes.len();
+                to_page.push((key, compressed));
+            }
+            None => break,
+        }
+    }
+
+    let results = pool.scope(|s| {
+        let db = &*db;
+        #[cfg(feature = "encryption")]
+        let encryption = &*encryption;
+        for (key, compressed) in to_page.into_iter() {
+            s.spawn(async move {
+                // After LZ4 compression, optionally encrypt before the bytes leave memory.
+                #[cfg(feature = "encryption")]
+                let bytes = super::encryption::encrypt(encryption, &compressed.compressed_bytes);
+                #[cfg(not(feature = "encryption"))]
+                let bytes = compressed.compressed_bytes;
+
+                (key, db.save(key, &bytes))
+            });
+        }
+    });
+
+    for (key, result) in results.into_iter() {
+        if let Err(e) = result {
+            warn!("Failed to page out chunk {:?}: {}", key, e);
+        }
+    }
+}
+
+impl<V> VoxelMap<V>
+where
+    V: Voxel,
+{
+    /// The disk-aware single-chunk get path: ensures `key` is resident in the compressed pool,
+    /// faulting it back from the dedup tier or the `ChunkDb` if it was paged out, and returns
+    /// whether the chunk exists at all (in memory or on disk).
+    ///
+    /// This is where fault-in actually happens — on a *miss*, when a caller asks for a chunk the
+    /// paging system has since evicted. Drive every cold single-chunk read through here (rather
+    /// than straight through `reader`) so paged-out chunks transparently come back.
+    pub fn get_chunk(
+        &mut self,
+        db: &ChunkDb,
+        key: Point3i,
+        #[cfg(feature = "encryption")] encryption: &crate::EncryptionConfig,
+    ) -> Result<bool, PersistenceError> {
+        if self.voxels.chunks.get_local(key).is_some() {
+            return Ok(true);
+        }
+
+        // A dedup'd key's buffer lives in the dedup cache, not the pool; rehydrate from there
+        // before paying for a disk round-trip.
+        if self.fault_in_dedup(key) {
+            return Ok(true);
+        }
+
+        self.fault_in(
+            db,
+            key,
+            #[cfg(feature = "encryption")]
+            encryption,
+        )
+    }
+
+    /// Faults a previously paged-out chunk back into the compressed pool from the `ChunkDb`, if it
+    /// isn't already resident, returning whether a chunk was actually restored. Prefer `get_chunk`,
+    /// which also checks the dedup tier; this is the lower-level disk primitive.
+    pub fn fault_in(
+        &mut self,
+        db: &ChunkDb,
+        key: Point3i,
+        #[cfg(feature = "encryption")] encryption: &crate::EncryptionConfig,
+    ) -> Result<bool, PersistenceError> {
+        if self.voxels.chunks.get_local(key).is_some() {
+            return Ok(false);
+        }
+
+        Ok(match db.load(key)? {
+            Some(bytes) => {
+                // Decrypt lazily, then decompress, mirroring the save path's cipher boundary. A
+                // corrupt or wrong-key blob surfaces as a PersistenceError rather than a panic.
+                #[cfg(feature = "encryption")]
+                let bytes = super::encryption::decrypt(encryption, &bytes)?;
+
+                let compressed = CompressedChunk::<V>::from_bytes(FastLz4 { level: 10 }, bytes);
+                self.voxels.chunks.insert_compressed(key, compressed);
+                true
+            }
+            None => false,
+        })
+    }
+
+    /// Rehydrates a deduplicated chunk from the dedup cache back into the compressed pool, returning
+    /// whether one was present. The shared buffer is cloned into the pool and the key's reference is
+    /// dropped from the cache, keeping `dedup_stats` in lockstep with the compressed tier.
+    fn fault_in_dedup(&mut self, key: Point3i) -> bool
+    where
+        CompressedChunk<V>: Clone,
+    {
+        match self.dedup.get(key) {
+            Some(shared) => {
+                self.voxels.chunks.insert_compressed(key, (*shared).clone());
+                self.dedup.remove(key);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use building_blocks::core::PointN;
+
+    #[test]
+    fn encode_key_is_a_stable_12_byte_layout() {
+        assert_eq!(
+            encode_key(PointN([1, 2, 3])),
+            [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3]
+        );
+    }
+
+    #[test]
+    fn encode_key_orders_non_negative_coordinates() {
+        // Big-endian encoding keeps non-negative keys in the same order as the points, so the
+        // backing B-tree stays sorted for O(log n) range lookups.
+        let a = encode_key(PointN([0, 0, 1]));
+        let b = encode_key(PointN([0, 1, 0]));
+        let c = encode_key(PointN([1, 0, 0]));
+        assert!(a < b);
+        assert!(b < c);
+    }
+}
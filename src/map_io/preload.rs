@@ -0,0 +1,128 @@
+use crate::{default_array, EditBuffer, Voxel, VoxelMap};
+
+use bevy::tasks::{ComputeTaskPool, Task};
+use building_blocks::{core::prelude::*, storage::prelude::*};
+
+use std::sync::Arc;
+
+/// A handle to an in-flight asynchronous preload, produced by `VoxelMap::preload_box_async`. Poll it
+/// for readiness, then hand the joined chunks to `VoxelMap::finish_preload` to write them through
+/// the `EditBuffer` without stalling the frame they were requested on.
+pub struct PreloadHandle<V> {
+    tasks: Vec<Task<(Point3i, Array3<V>)>>,
+}
+
+impl<V> PreloadHandle<V>
+where
+    V: Voxel,
+{
+    /// Returns `true` once every generation task has finished and `join` won't block.
+    pub fn is_ready(&self) -> bool {
+        self.tasks.iter().all(|task| task.is_finished())
+    }
+
+    /// Blocks until generation completes, yielding the freshly generated chunks.
+    pub fn join(self) -> Vec<(Point3i, Array3<V>)> {
+        self.tasks
+            .into_iter()
+            .map(|task| futures_lite::future::block_on(task))
+            .collect()
+    }
+}
+
+impl<V> VoxelMap<V>
+where
+    V: Voxel,
+{
+    /// Generates every chunk intersecting `extent` that isn't already resident, filling each voxel
+    /// via `generator`, then writes the new chunks through `edit_buffer` so they flow through the
+    /// same double-buffering pipeline (and dirty tracking) as every other edit. Generation is
+    /// fanned out across the `ComputeTaskPool`.
+    ///
+    /// This warms up a region in one call, instead of driving chunk creation one voxel at a time.
+    pub fn preload_box(
+        &self,
+        extent: Extent3i,
+        pool: &ComputeTaskPool,
+        edit_buffer: &mut EditBuffer<V>,
+        generator: impl Fn(Point3i) -> V + Send + Sync,
+    ) {
+        let new_chunks = self.generate_missing_chunks(extent, pool, &generator);
+        for (chunk_key, array) in new_chunks.into_iter() {
+            edit_buffer.write_chunk(chunk_key, array);
+        }
+    }
+
+    /// An asynchronous variant of `preload_box` that spawns one generation task per missing chunk on
+    /// the `ComputeTaskPool` and returns a `PreloadHandle` so callers can poll for completion without
+    /// blocking. Once ready, pass `PreloadHandle::join` to `finish_preload`.
+    pub fn preload_box_async(
+        &self,
+        extent: Extent3i,
+        pool: &ComputeTaskPool,
+        generator: impl Fn(Point3i) -> V + Send + Sync + 'static,
+    ) -> PreloadHandle<V> {
+        let chunk_shape = self.voxels.indexer.chunk_shape();
+        let generator = Arc::new(generator);
+
+        let tasks = self
+            .missing_chunk_keys(extent)
+            .into_iter()
+            .map(|chunk_key| {
+                let generator = generator.clone();
+                pool.spawn(async move {
+                    (chunk_key, generate_chunk(chunk_key, chunk_shape, &*generator))
+                })
+            })
+            .collect();
+
+        PreloadHandle { tasks }
+    }
+
+    /// Writes chunks produced by `preload_box_async` through `edit_buffer`.
+    pub fn finish_preload(
+        &self,
+        chunks: Vec<(Point3i, Array3<V>)>,
+        edit_buffer: &mut EditBuffer<V>,
+    ) {
+        for (chunk_key, array) in chunks.into_iter() {
+            edit_buffer.write_chunk(chunk_key, array);
+        }
+    }
+
+    /// The keys of all chunks intersecting `extent` that are not yet resident in the map.
+    fn missing_chunk_keys(&self, extent: Extent3i) -> Vec<Point3i> {
+        self.voxels
+            .indexer
+            .chunk_keys_for_extent(&extent)
+            .filter(|&key| self.voxels.get_chunk(key).is_none())
+            .collect()
+    }
+
+    fn generate_missing_chunks(
+        &self,
+        extent: Extent3i,
+        pool: &ComputeTaskPool,
+        generator: &(impl Fn(Point3i) -> V + Send + Sync),
+    ) -> Vec<(Point3i, Array3<V>)> {
+        let chunk_shape = self.voxels.indexer.chunk_shape();
+
+        pool.scope(|s| {
+            for chunk_key in self.missing_chunk_keys(extent).into_iter() {
+                s.spawn(async move { (chunk_key, generate_chunk(chunk_key, chunk_shape, generator)) })
+            }
+        })
+    }
+}
+
+/// Fills a single chunk array at `chunk_key` by evaluating `generator` at every point.
+fn generate_chunk<V>(chunk_key: Point3i, chunk_shape: Point3i, generator: &impl Fn(Point3i) -> V) -> Array3<V>
+where
+    V: Voxel,
+{
+    let chunk_extent = Extent3i::from_min_and_shape(chunk_key, chunk_shape);
+    let mut array = default_array::<V>(chunk_extent);
+    array.for_each_mut(&chunk_extent, |p: Point3i, v| *v = generator(p));
+
+    array
+}
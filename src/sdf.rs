@@ -0,0 +1,151 @@
+use crate::Voxel;
+
+use building_blocks::storage::IsEmpty;
+
+/// A voxel capability for representing smooth surfaces via a signed distance field.
+///
+/// Unlike a pure type index, which forces blocky geometry, the signed distance lets a surface-nets
+/// or dual-contouring mesher reconstruct a smooth isosurface at `distance == 0`. The distance is
+/// negative inside solid matter and positive in empty space.
+pub trait SignedDistance: Copy {
+    fn distance(&self) -> f32;
+}
+
+/// The range `[-1.0, 1.0]` that `Sd8`/`Sd16` quantize onto their integer range. Distances outside
+/// this band saturate to the nearest endpoint.
+const SDF_RANGE: f32 = 1.0;
+
+/// A signed distance quantized into an `i8`, mapping `[-1.0, 1.0]` onto `-i8::MAX..=i8::MAX`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Sd8(pub i8);
+
+/// A signed distance quantized into an `i16`, mapping `[-1.0, 1.0]` onto `-i16::MAX..=i16::MAX`. This
+/// is the precision expected by the meshers, so `VoxelMap::sdf_transform` yields an `Sd16` view.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Sd16(pub i16);
+
+impl From<f32> for Sd8 {
+    #[inline]
+    fn from(d: f32) -> Self {
+        Sd8((d.clamp(-SDF_RANGE, SDF_RANGE) / SDF_RANGE * i8::MAX as f32).round() as i8)
+    }
+}
+
+impl From<Sd8> for f32 {
+    #[inline]
+    fn from(s: Sd8) -> Self {
+        s.0 as f32 / i8::MAX as f32 * SDF_RANGE
+    }
+}
+
+impl From<f32> for Sd16 {
+    #[inline]
+    fn from(d: f32) -> Self {
+        Sd16((d.clamp(-SDF_RANGE, SDF_RANGE) / SDF_RANGE * i16::MAX as f32).round() as i16)
+    }
+}
+
+impl From<Sd16> for f32 {
+    #[inline]
+    fn from(s: Sd16) -> Self {
+        s.0 as f32 / i16::MAX as f32 * SDF_RANGE
+    }
+}
+
+impl SignedDistance for Sd8 {
+    #[inline]
+    fn distance(&self) -> f32 {
+        (*self).into()
+    }
+}
+
+impl SignedDistance for Sd16 {
+    #[inline]
+    fn distance(&self) -> f32 {
+        (*self).into()
+    }
+}
+
+// A voxel is occupied exactly where its signed distance reaches the surface, so positive distance is
+// "empty" as far as the octree generator is concerned.
+impl IsEmpty for &Sd8 {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.distance() > 0.0
+    }
+}
+
+impl IsEmpty for &Sd16 {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.distance() > 0.0
+    }
+}
+
+/// A composite voxel that carries both a type index and a signed distance, so a single map can drive
+/// blocky type-based occupancy (via `IsEmpty` on the type's info) *and* smooth SDF meshing. This is
+/// the "second parallel channel" for users who want both; pure type-index or pure SDF voxels can
+/// keep using their own types instead.
+#[derive(Clone, Copy, Default)]
+pub struct SdfVoxel<V> {
+    pub voxel_type: V,
+    pub distance: Sd16,
+}
+
+impl<V> Voxel for SdfVoxel<V>
+where
+    V: Voxel,
+{
+    type TypeInfo = V::TypeInfo;
+
+    #[inline]
+    fn get_type_index(&self) -> usize {
+        self.voxel_type.get_type_index()
+    }
+}
+
+impl<V> SignedDistance for SdfVoxel<V>
+where
+    V: Copy,
+{
+    #[inline]
+    fn distance(&self) -> f32 {
+        self.distance.distance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sd16_round_trips_within_quantization_error() {
+        for &d in &[-1.0, -0.5, -0.01, 0.0, 0.25, 0.75, 1.0] {
+            let round_tripped: f32 = Sd16::from(d).into();
+            assert!((round_tripped - d).abs() < 1.0 / i16::MAX as f32);
+        }
+    }
+
+    #[test]
+    fn sd8_round_trips_within_quantization_error() {
+        for &d in &[-1.0, -0.5, 0.0, 0.5, 1.0] {
+            let round_tripped: f32 = Sd8::from(d).into();
+            assert!((round_tripped - d).abs() < 1.0 / i8::MAX as f32);
+        }
+    }
+
+    #[test]
+    fn distances_outside_the_band_saturate() {
+        assert_eq!(Sd16::from(5.0), Sd16(i16::MAX));
+        assert_eq!(Sd16::from(-5.0), Sd16(-i16::MAX));
+        assert_eq!(Sd8::from(5.0), Sd8(i8::MAX));
+        assert_eq!(Sd8::from(-5.0), Sd8(-i8::MAX));
+    }
+
+    #[test]
+    fn occupancy_follows_the_sign_of_the_distance() {
+        assert!((&Sd16::from(0.5)).is_empty());
+        assert!(!(&Sd16::from(-0.5)).is_empty());
+        assert!(!(&Sd16::from(0.0)).is_empty());
+    }
+}